@@ -1,78 +1,313 @@
 //! Functions to generate point clouds
 
-use std::{f64::consts::PI, ops::Range};
+use std::{
+    collections::{HashMap, HashSet},
+    f64::consts::PI,
+    ops::Range,
+};
 
 use rand::Rng;
 
-/// Helper: add i.i.d. uniform noise in `noise_range` to every coordinate in-place
-fn add_noise(points: &mut [Vec<f64>], noise_range: Option<Range<f64>>) {
-    if let Some(r) = noise_range {
-        let mut rng = rand::thread_rng();
-        for p in points.iter_mut() {
-            for coord in p.iter_mut() {
-                *coord += rng.gen_range(r.clone());
+/// A noise model that can be applied to a generated point cloud by [`add_noise`]
+pub enum NoiseModel {
+    /// Add i.i.d. uniform noise drawn from the given range to every coordinate
+    Uniform(Range<f64>),
+    /// Add i.i.d. Gaussian noise N(0, stddev²) to every coordinate
+    Gaussian { stddev: f64 },
+    /// Replace each point, independently with probability `fraction`, with an outlier drawn
+    /// uniformly per coordinate from `bounds`, independent of the original point
+    Outliers { fraction: f64, bounds: Range<f64> },
+}
+
+/// Helper: apply `noise`, if any, to the points in-place
+fn add_noise(points: &mut [Vec<f64>], noise: Option<NoiseModel>) {
+    let Some(model) = noise else { return };
+    let mut rng = rand::thread_rng();
+    match model {
+        NoiseModel::Uniform(r) => {
+            for p in points.iter_mut() {
+                for coord in p.iter_mut() {
+                    *coord += rng.gen_range(r.clone());
+                }
+            }
+        }
+        NoiseModel::Gaussian { stddev } => {
+            for p in points.iter_mut() {
+                for coord in p.iter_mut() {
+                    *coord += stddev * sample_standard_normal(&mut rng);
+                }
             }
         }
+        NoiseModel::Outliers { fraction, bounds } => {
+            for p in points.iter_mut() {
+                if rng.gen::<f64>() < fraction {
+                    for coord in p.iter_mut() {
+                        *coord = rng.gen_range(bounds.clone());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Draw a single standard normal variate via the Box–Muller transform
+fn sample_standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u1: f64 = rng.gen::<f64>().max(f64::MIN_POSITIVE);
+    let u2: f64 = rng.gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+/// Sample a uniformly random direction in R^dim via Gaussian normalization (the Muller
+/// method): draw `dim` i.i.d. standard normals and normalize by their Euclidean norm. This
+/// is exactly uniform on the (dim-1)-sphere in any dimension, and avoids the angle
+/// parametrizations that [`sample_unit_direction3`] needs special-cased per dimension.
+/// Resamples on the vanishingly rare all-zero Gaussian draw, which would otherwise divide
+/// by zero.
+fn sample_unit_direction_n<R: Rng + ?Sized>(dim: usize, rng: &mut R) -> Vec<f64> {
+    assert!(dim > 0, "dim must be positive");
+    loop {
+        let g: Vec<f64> = (0..dim).map(|_| sample_standard_normal(rng)).collect();
+        let norm = g.iter().map(|x| x * x).sum::<f64>().sqrt();
+        if norm > 0.0 {
+            return g.into_iter().map(|x| x / norm).collect();
+        }
+    }
+}
+
+/// Sample a uniformly random direction on the unit 2-sphere in R^3
+///
+/// Sampling: theta ~ U[0, 2π), z ~ U[-1, 1], x = √(1-z²) cos theta, y = √(1-z²) sin theta.
+fn sample_unit_direction3<R: Rng + ?Sized>(rng: &mut R) -> [f64; 3] {
+    let theta: f64 = rng.gen_range(0.0..(2.0 * PI));
+    let z: f64 = rng.gen_range(-1.0..1.0);
+    let r_xy = (1.0 - z * z).sqrt();
+    [r_xy * theta.cos(), r_xy * theta.sin(), z]
+}
+
+/// A shape that can draw a random point from its boundary or interior given any `Rng`,
+/// so callers can seed their own RNG for reproducible point clouds.
+pub trait ShapeSample {
+    /// Uniformly sample a single point from the boundary of the shape.
+    fn sample_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64>;
+
+    /// Uniformly sample a single point from the interior of the shape.
+    fn sample_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64>;
+
+    /// Draw `m` independent points from the boundary of the shape.
+    fn sample_n<R: Rng + ?Sized>(&self, m: usize, rng: &mut R) -> Vec<Vec<f64>> {
+        (0..m).map(|_| self.sample_boundary(rng)).collect()
+    }
+}
+
+/// A circle of given `radius` centered at the origin in R^2
+pub struct Circle {
+    pub radius: f64,
+}
+
+impl ShapeSample for Circle {
+    fn sample_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let theta = rng.gen_range(0.0..(2.0 * PI));
+        vec![self.radius * theta.cos(), self.radius * theta.sin()]
+    }
+
+    fn sample_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let theta = rng.gen_range(0.0..(2.0 * PI));
+        let r = self.radius * rng.gen::<f64>().sqrt();
+        vec![r * theta.cos(), r * theta.sin()]
+    }
+}
+
+/// The surface of a sphere of given `radius` centered at the origin in R^3
+pub struct SphereSurface {
+    pub radius: f64,
+}
+
+impl ShapeSample for SphereSurface {
+    fn sample_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let dir = sample_unit_direction3(rng);
+        vec![self.radius * dir[0], self.radius * dir[1], self.radius * dir[2]]
+    }
+
+    fn sample_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        Ball { radius: self.radius }.sample_interior(rng)
+    }
+}
+
+/// A solid ball of given `radius` centered at the origin in R^3
+pub struct Ball {
+    pub radius: f64,
+}
+
+impl ShapeSample for Ball {
+    fn sample_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        SphereSurface { radius: self.radius }.sample_boundary(rng)
+    }
+
+    fn sample_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let dir = sample_unit_direction3(rng);
+        // radius for uniform volume
+        let r = self.radius * rng.gen::<f64>().cbrt();
+        vec![r * dir[0], r * dir[1], r * dir[2]]
+    }
+}
+
+/// A torus surface embedded in R^3, with `major` the distance from the center to the tube
+/// center and `minor` the tube radius
+///
+/// Parametrization: (x, y, z) = ((major + minor cos v) cos u, (major + minor cos v) sin u, minor sin v)
+pub struct Torus {
+    pub major: f64,
+    pub minor: f64,
+}
+
+impl ShapeSample for Torus {
+    /// Sampling: u ~ U[0, 2π) directly; v ~ U[0, 2π) by rejection, since the surface area
+    /// element is proportional to (major + minor·cos v), so drawing v uniformly would
+    /// over-sample the inner rim of the tube and under-sample the outer rim. A candidate v
+    /// is accepted with probability (major + minor·cos v) / (major + minor), the max of the
+    /// weight, which is exactly the Jacobian normalized to [0, 1].
+    fn sample_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let u = rng.gen_range(0.0..(2.0 * PI));
+        let v = loop {
+            let candidate = rng.gen_range(0.0..(2.0 * PI));
+            let weight = (self.major + self.minor * candidate.cos()) / (self.major + self.minor);
+            if rng.gen::<f64>() <= weight {
+                break candidate;
+            }
+        };
+        let cx = (self.major + self.minor * v.cos()) * u.cos();
+        let cy = (self.major + self.minor * v.cos()) * u.sin();
+        let cz = self.minor * v.sin();
+        vec![cx, cy, cz]
     }
+
+    /// Sampling: u ~ U[0, 2π) directly; (rho, v) drawn by the same rejection test as
+    /// [`Self::sample_boundary`], extended over the minor radius: rho ~ minor·√U[0,1) for
+    /// volume-uniformity along the tube radius, v accepted with probability
+    /// (major + rho·cos v) / (major + minor).
+    fn sample_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let u = rng.gen_range(0.0..(2.0 * PI));
+        let (rho, v) = loop {
+            let rho = self.minor * rng.gen::<f64>().sqrt();
+            let candidate = rng.gen_range(0.0..(2.0 * PI));
+            let weight = (self.major + rho * candidate.cos()) / (self.major + self.minor);
+            if rng.gen::<f64>() <= weight {
+                break (rho, candidate);
+            }
+        };
+        let cx = (self.major + rho * v.cos()) * u.cos();
+        let cy = (self.major + rho * v.cos()) * u.sin();
+        let cz = rho * v.sin();
+        vec![cx, cy, cz]
+    }
+}
+
+/// The surface of a (dim-1)-sphere of given `radius` centered at the origin in R^dim
+pub struct NSphereSurface {
+    pub dim: usize,
+    pub radius: f64,
+}
+
+impl ShapeSample for NSphereSurface {
+    fn sample_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        sample_unit_direction_n(self.dim, rng)
+            .into_iter()
+            .map(|x| x * self.radius)
+            .collect()
+    }
+
+    fn sample_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        NBall { dim: self.dim, radius: self.radius }.sample_interior(rng)
+    }
+}
+
+/// A solid ball of given `radius` centered at the origin in R^dim
+pub struct NBall {
+    pub dim: usize,
+    pub radius: f64,
 }
 
-/// Return `m` points evenly spaced on the unit circle (optionally with uniform noise per coord)
-pub fn unit_circle(m: usize, noise_range: Option<Range<f64>>) -> Vec<Vec<f64>> {
-    let circpoint = |k: usize| {
+impl ShapeSample for NBall {
+    fn sample_boundary<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        NSphereSurface { dim: self.dim, radius: self.radius }.sample_boundary(rng)
+    }
+
+    /// Sampling: direction from the (dim-1)-sphere via Gaussian normalization; radius =
+    /// U[0,1]^(1/dim) for volume-uniformity.
+    fn sample_interior<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<f64> {
+        let dir = sample_unit_direction_n(self.dim, rng);
+        let r = self.radius * rng.gen::<f64>().powf(1.0 / self.dim as f64);
+        dir.into_iter().map(|x| x * r).collect()
+    }
+}
+
+/// Return `m` points evenly spaced on the unit circle (optionally perturbed by `noise`)
+///
+/// Unlike the other generators in this module, the points are laid out deterministically
+/// as a regular `m`-gon rather than drawn via `Circle::sample_boundary`, so repeated calls
+/// with the same `m` always produce the same (unperturbed) layout.
+pub fn unit_circle(m: usize, noise: Option<NoiseModel>) -> Vec<Vec<f64>> {
+    let circle_point = |k: usize| {
         let theta = k as f64 * 2.0 * PI / m as f64;
         vec![theta.cos(), theta.sin()]
     };
-    let mut pts: Vec<_> = (0..m).map(circpoint).collect();
-    add_noise(&mut pts, noise_range);
+    let mut pts: Vec<_> = (0..m).map(circle_point).collect();
+    add_noise(&mut pts, noise);
     pts
 }
 
 /// Return `m` points uniformly distributed on the unit 2-sphere surface in R^3
-///
-/// Sampling: theta ~ U[0, 2π), z ~ U[-1, 1], x = √(1-z²) cos theta, y = √(1-z²) sin theta.
-pub fn unit_sphere_surface(m: usize, noise_range: Option<Range<f64>>) -> Vec<Vec<f64>> {
+pub fn unit_sphere_surface(m: usize, noise: Option<NoiseModel>) -> Vec<Vec<f64>> {
     let mut rng = rand::thread_rng();
-    let mut pts = Vec::with_capacity(m);
-    for _ in 0..m {
-        let theta = rng.gen_range(0.0..(2.0 * PI));
-        let z = rng.gen_range(-1.0..1.0);
-        let r = (1.0 - z * z).sqrt();
-        let x = r * theta.cos();
-        let y = r * theta.sin();
-        pts.push(vec![x, y, z]);
-    }
-    add_noise(&mut pts, noise_range);
+    let mut pts = SphereSurface { radius: 1.0 }.sample_n(m, &mut rng);
+    add_noise(&mut pts, noise);
     pts
 }
 
 /// Return `m` points uniformly distributed *inside* a unit 3D ball (not just the surface)
-///
-/// Sampling: direction from unit sphere; radius = U[0,1]^(1/3) for volume-uniformity.
-pub fn unit_ball(m: usize, noise_range: Option<Range<f64>>) -> Vec<Vec<f64>> {
+pub fn unit_ball(m: usize, noise: Option<NoiseModel>) -> Vec<Vec<f64>> {
     let mut rng = rand::thread_rng();
-    let mut pts = Vec::with_capacity(m);
-    for _ in 0..m {
-        // direction
-        let theta = rng.gen_range(0.0..(2.0 * PI));
-        let z = rng.gen_range(-1.0..1.0);
-        let r_xy = (1.0 - z * z).sqrt();
-        let dir = [r_xy * theta.cos(), r_xy * theta.sin(), z];
+    let ball = Ball { radius: 1.0 };
+    let mut pts: Vec<_> = (0..m).map(|_| ball.sample_interior(&mut rng)).collect();
+    add_noise(&mut pts, noise);
+    pts
+}
 
-        // radius for uniform volume
-        let r = rng.gen::<f64>().cbrt();
+/// Return `m` points uniformly distributed on a torus surface embedded in R^3
+///
+/// `major` = R (distance from center to tube center), `minor` = r (tube radius). Uses
+/// rejection sampling so the cloud is uniform with respect to surface area; see
+/// [`Torus::sample_boundary`] for the acceptance test. For the old (non-uniform) behavior,
+/// see [`torus_naive`].
+pub fn torus(m: usize, major: f64, minor: f64, noise: Option<NoiseModel>) -> Vec<Vec<f64>> {
+    assert!(major > 0.0 && minor > 0.0, "major and minor radii must be positive");
+    let mut rng = rand::thread_rng();
+    let mut pts = Torus { major, minor }.sample_n(m, &mut rng);
+    add_noise(&mut pts, noise);
+    pts
+}
 
-        pts.push(vec![r * dir[0], r * dir[1], r * dir[2]]);
-    }
-    add_noise(&mut pts, noise_range);
+/// Return `m` points uniformly distributed *inside* a solid torus (the tube's interior, not
+/// just its surface) embedded in R^3
+///
+/// `major` = R (distance from center to tube center), `minor` = r (tube radius). See
+/// [`Torus::sample_interior`] for the volume-uniform rejection sampling scheme.
+pub fn solid_torus(m: usize, major: f64, minor: f64, noise: Option<NoiseModel>) -> Vec<Vec<f64>> {
+    assert!(major > 0.0 && minor > 0.0, "major and minor radii must be positive");
+    let mut rng = rand::thread_rng();
+    let torus = Torus { major, minor };
+    let mut pts: Vec<_> = (0..m).map(|_| torus.sample_interior(&mut rng)).collect();
+    add_noise(&mut pts, noise);
     pts
 }
 
-/// Return `m` points on a torus surface embedded in R^3
+/// Return `m` points on a torus surface embedded in R^3, using the original (non-uniform)
+/// parametrization `u, v ~ U[0, 2π)` independently
 ///
-/// Parametrization: u,v ~ U[0,2π)
-/// (x, y, z) = ((R + r cos v) cos u, (R + r cos v) sin u, r sin v)
-/// `major` = R (distance from center to tube center), `minor` = r (tube radius)
-pub fn torus(m: usize, major: f64, minor: f64, noise_range: Option<Range<f64>>) -> Vec<Vec<f64>> {
+/// This over-samples the inner rim of the tube and under-samples the outer rim, since the
+/// surface area element is proportional to `(major + minor·cos v)`. Kept for callers that
+/// relied on the old behavior; prefer [`torus`] for a surface-area-uniform cloud.
+pub fn torus_naive(m: usize, major: f64, minor: f64, noise: Option<NoiseModel>) -> Vec<Vec<f64>> {
     assert!(major > 0.0 && minor > 0.0, "major and minor radii must be positive");
     let mut rng = rand::thread_rng();
     let mut pts = Vec::with_capacity(m);
@@ -84,6 +319,374 @@ pub fn torus(m: usize, major: f64, minor: f64, noise_range: Option<Range<f64>>)
         let cz = minor * v.sin();
         pts.push(vec![cx, cy, cz]);
     }
-    add_noise(&mut pts, noise_range);
+    add_noise(&mut pts, noise);
     pts
-}
\ No newline at end of file
+}
+
+/// Return `m` points uniformly distributed on the unit (dim-1)-sphere surface in R^dim
+///
+/// Generalizes [`unit_sphere_surface`] to arbitrary dimension via [`NSphereSurface`]'s
+/// Gaussian-normalization sampling.
+pub fn unit_nsphere_surface(m: usize, dim: usize, noise: Option<NoiseModel>) -> Vec<Vec<f64>> {
+    let mut rng = rand::thread_rng();
+    let mut pts = NSphereSurface { dim, radius: 1.0 }.sample_n(m, &mut rng);
+    add_noise(&mut pts, noise);
+    pts
+}
+
+/// Return `m` points uniformly distributed *inside* a unit ball in R^dim
+///
+/// Generalizes [`unit_ball`] to arbitrary dimension via [`NBall`]'s Gaussian-normalization
+/// sampling.
+pub fn unit_nball(m: usize, dim: usize, noise: Option<NoiseModel>) -> Vec<Vec<f64>> {
+    let mut rng = rand::thread_rng();
+    let ball = NBall { dim, radius: 1.0 };
+    let mut pts: Vec<_> = (0..m).map(|_| ball.sample_interior(&mut rng)).collect();
+    add_noise(&mut pts, noise);
+    pts
+}
+
+/// Return a triangulated mesh of the unit sphere, obtained by subdividing an icosahedron
+/// `subdivisions` times
+///
+/// Starts from the 12 icosahedron vertices and 20 faces; each subdivision splits every
+/// triangle into 4 by inserting an edge-midpoint (projected back onto the unit sphere),
+/// cached so the two triangles sharing an edge reuse the same vertex.
+pub fn icosphere(subdivisions: usize) -> (Vec<Vec<f64>>, Vec<[usize; 3]>) {
+    let t = (1.0 + 5.0_f64.sqrt()) / 2.0;
+    let raw_vertices = [
+        [-1.0, t, 0.0], [1.0, t, 0.0], [-1.0, -t, 0.0], [1.0, -t, 0.0],
+        [0.0, -1.0, t], [0.0, 1.0, t], [0.0, -1.0, -t], [0.0, 1.0, -t],
+        [t, 0.0, -1.0], [t, 0.0, 1.0], [-t, 0.0, -1.0], [-t, 0.0, 1.0],
+    ];
+    let mut vertices: Vec<Vec<f64>> = raw_vertices.iter().map(normalize_to_unit_sphere).collect();
+
+    let mut faces: Vec<[usize; 3]> = vec![
+        [0, 11, 5], [0, 5, 1], [0, 1, 7], [0, 7, 10], [0, 10, 11],
+        [1, 5, 9], [5, 11, 4], [11, 10, 2], [10, 7, 6], [7, 1, 8],
+        [3, 9, 4], [3, 4, 2], [3, 2, 6], [3, 6, 8], [3, 8, 9],
+        [4, 9, 5], [2, 4, 11], [6, 2, 10], [8, 6, 7], [9, 8, 1],
+    ];
+
+    for _ in 0..subdivisions {
+        let mut midpoint_cache: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut next_faces = Vec::with_capacity(faces.len() * 4);
+        for [a, b, c] in faces {
+            let ab = icosphere_midpoint(&mut vertices, &mut midpoint_cache, a, b);
+            let bc = icosphere_midpoint(&mut vertices, &mut midpoint_cache, b, c);
+            let ca = icosphere_midpoint(&mut vertices, &mut midpoint_cache, c, a);
+            next_faces.push([a, ab, ca]);
+            next_faces.push([b, bc, ab]);
+            next_faces.push([c, ca, bc]);
+            next_faces.push([ab, bc, ca]);
+        }
+        faces = next_faces;
+    }
+
+    (vertices, faces)
+}
+
+/// Helper: rescale a vector to lie on the unit sphere
+fn normalize_to_unit_sphere(v: &[f64; 3]) -> Vec<f64> {
+    let norm = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    vec![v[0] / norm, v[1] / norm, v[2] / norm]
+}
+
+/// Helper: return the index of the unit-sphere-projected midpoint of edge `(a, b)`, inserting
+/// it into `vertices` and caching it in `midpoint_cache` the first time the edge is seen, so
+/// the two triangles sharing an edge reuse the same vertex instead of creating a duplicate
+fn icosphere_midpoint(
+    vertices: &mut Vec<Vec<f64>>,
+    midpoint_cache: &mut HashMap<(usize, usize), usize>,
+    a: usize,
+    b: usize,
+) -> usize {
+    let key = if a < b { (a, b) } else { (b, a) };
+    if let Some(&idx) = midpoint_cache.get(&key) {
+        return idx;
+    }
+    let mid = [
+        (vertices[a][0] + vertices[b][0]) / 2.0,
+        (vertices[a][1] + vertices[b][1]) / 2.0,
+        (vertices[a][2] + vertices[b][2]) / 2.0,
+    ];
+    vertices.push(normalize_to_unit_sphere(&mid));
+    let idx = vertices.len() - 1;
+    midpoint_cache.insert(key, idx);
+    idx
+}
+
+/// Return `m` points lying (approximately) on the zero level set of `f`, extracted by
+/// marching cubes over a voxel grid spanning `bounds` at `resolution` subdivisions per axis
+///
+/// Each grid cube is classified by the sign of `f` at its 8 corners; crossing edges are
+/// linearly interpolated and chained into triangles per-cube (see [`marching_cube`]), with
+/// the ambiguous "checkerboard" face case resolved via the bilinear asymptotic decider so
+/// faces don't tear. Points are then drawn from the resulting mesh, area-weighted by
+/// triangle, via barycentric sampling.
+pub fn implicit_surface(
+    f: impl Fn([f64; 3]) -> f64,
+    bounds: [Range<f64>; 3],
+    resolution: usize,
+    m: usize,
+    noise: Option<NoiseModel>,
+) -> Vec<Vec<f64>> {
+    assert!(resolution > 0, "resolution must be positive");
+    let steps = resolution + 1;
+    let step_size = [
+        (bounds[0].end - bounds[0].start) / resolution as f64,
+        (bounds[1].end - bounds[1].start) / resolution as f64,
+        (bounds[2].end - bounds[2].start) / resolution as f64,
+    ];
+    let grid_point = |i: usize, j: usize, k: usize| -> [f64; 3] {
+        [
+            bounds[0].start + i as f64 * step_size[0],
+            bounds[1].start + j as f64 * step_size[1],
+            bounds[2].start + k as f64 * step_size[2],
+        ]
+    };
+    let index = |i: usize, j: usize, k: usize| (i * steps + j) * steps + k;
+
+    let mut values = vec![0.0; steps * steps * steps];
+    for i in 0..steps {
+        for j in 0..steps {
+            for k in 0..steps {
+                let value = f(grid_point(i, j, k));
+                assert!(value.is_finite(), "f returned a non-finite value at grid point ({i}, {j}, {k})");
+                values[index(i, j, k)] = value;
+            }
+        }
+    }
+
+    const CORNER_OFFSETS: [[usize; 3]; 8] = [
+        [0, 0, 0], [1, 0, 0], [1, 1, 0], [0, 1, 0],
+        [0, 0, 1], [1, 0, 1], [1, 1, 1], [0, 1, 1],
+    ];
+    let mut triangles: Vec<[[f64; 3]; 3]> = Vec::new();
+    for i in 0..resolution {
+        for j in 0..resolution {
+            for k in 0..resolution {
+                let corners = CORNER_OFFSETS.map(|[di, dj, dk]| grid_point(i + di, j + dj, k + dk));
+                let vals = CORNER_OFFSETS.map(|[di, dj, dk]| values[index(i + di, j + dj, k + dk)]);
+                marching_cube(corners, vals, &mut triangles);
+            }
+        }
+    }
+
+    sample_triangle_mesh(&triangles, m, noise)
+}
+
+/// The 6 faces of a cube, each as the 4 corner indices (into [`CORNER_OFFSETS`]-order
+/// corners/values) listed in cyclic order around the face
+const CUBE_FACES: [[usize; 4]; 6] = [
+    [0, 1, 2, 3], // bottom (z = 0)
+    [4, 5, 6, 7], // top (z = 1)
+    [0, 1, 5, 4], // front (y = 0)
+    [3, 2, 6, 7], // back (y = 1)
+    [0, 3, 7, 4], // left (x = 0)
+    [1, 2, 6, 5], // right (x = 1)
+];
+
+/// Helper: triangulate a single cube's intersection with the `f = 0` level set (classic
+/// marching cubes), given its 8 corner positions/values in [`CORNER_OFFSETS`] order,
+/// appending any resulting triangles to `out`
+///
+/// Rather than a 256-entry case table, each of the cube's 6 faces is resolved independently:
+/// a face has 0, 2, or 4 of its edges crossing zero (never odd, since signs alternate around
+/// a cycle). 2 crossings pair unambiguously; the ambiguous 4-crossing "checkerboard" case is
+/// resolved via the bilinear asymptotic decider, matching how classic marching cubes avoids
+/// tearing a face shared between neighboring cubes. Each crossing edge borders exactly 2
+/// faces, so the chosen face segments always chain into closed loops, which are then
+/// fan-triangulated.
+fn marching_cube(corners: [[f64; 3]; 8], vals: [f64; 8], out: &mut Vec<[[f64; 3]; 3]>) {
+    let edge_key = |a: usize, b: usize| -> (usize, usize) { if a < b { (a, b) } else { (b, a) } };
+
+    let mut segments: Vec<((usize, usize), (usize, usize))> = Vec::new();
+    for face in CUBE_FACES {
+        let fv = face.map(|c| vals[c]);
+        let crossing: Vec<usize> = (0..4).filter(|&i| (fv[i] >= 0.0) != (fv[(i + 1) % 4] >= 0.0)).collect();
+        let key = |i: usize| edge_key(face[i], face[(i + 1) % 4]);
+        match crossing.len() {
+            0 => {}
+            2 => segments.push((key(crossing[0]), key(crossing[1]))),
+            4 => {
+                // checkerboard case: fv[0], fv[2] share a sign and fv[1], fv[3] share the other
+                let (v0, v1, v2, v3) = (fv[0], fv[1], fv[2], fv[3]);
+                let denom = v0 - v1 + v2 - v3;
+                let connect_0_2 = denom.abs() < 1e-12 || (v0 * v2 - v1 * v3) / denom * v0 >= 0.0;
+                if connect_0_2 {
+                    segments.push((key(0), key(1)));
+                    segments.push((key(2), key(3)));
+                } else {
+                    segments.push((key(3), key(0)));
+                    segments.push((key(1), key(2)));
+                }
+            }
+            _ => unreachable!("a cube face always has an even number of sign-crossing edges"),
+        }
+    }
+    if segments.is_empty() {
+        return;
+    }
+
+    let mut neighbors: HashMap<(usize, usize), Vec<(usize, usize)>> = HashMap::new();
+    for &(a, b) in &segments {
+        neighbors.entry(a).or_default().push(b);
+        neighbors.entry(b).or_default().push(a);
+    }
+
+    let lerp_edge = |(a, b): (usize, usize)| -> [f64; 3] {
+        let t = vals[a] / (vals[a] - vals[b]);
+        [
+            corners[a][0] + t * (corners[b][0] - corners[a][0]),
+            corners[a][1] + t * (corners[b][1] - corners[a][1]),
+            corners[a][2] + t * (corners[b][2] - corners[a][2]),
+        ]
+    };
+
+    let mut visited: HashSet<(usize, usize)> = HashSet::new();
+    let keys: Vec<(usize, usize)> = neighbors.keys().copied().collect();
+    for start in keys {
+        if visited.contains(&start) {
+            continue;
+        }
+        let mut loop_keys = vec![start];
+        visited.insert(start);
+        let mut prev = start;
+        let mut current = neighbors[&start][0];
+        while current != start {
+            loop_keys.push(current);
+            visited.insert(current);
+            let candidates = &neighbors[&current];
+            let next = if candidates[0] != prev { candidates[0] } else { candidates[1] };
+            prev = current;
+            current = next;
+        }
+        let loop_points: Vec<[f64; 3]> = loop_keys.iter().map(|&key| lerp_edge(key)).collect();
+        for i in 1..loop_points.len() - 1 {
+            out.push([loop_points[0], loop_points[i], loop_points[i + 1]]);
+        }
+    }
+}
+
+/// Helper: the area of a triangle given its 3 vertex positions in R^3
+fn triangle_area(t: &[[f64; 3]; 3]) -> f64 {
+    let u = [t[1][0] - t[0][0], t[1][1] - t[0][1], t[1][2] - t[0][2]];
+    let v = [t[2][0] - t[0][0], t[2][1] - t[0][1], t[2][2] - t[0][2]];
+    let cross = [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ];
+    0.5 * (cross[0] * cross[0] + cross[1] * cross[1] + cross[2] * cross[2]).sqrt()
+}
+
+/// Helper: sample `m` points from a triangle mesh, selecting each triangle with probability
+/// proportional to its area and drawing a uniformly random point inside it via barycentric
+/// coordinates (reflecting samples that land outside the triangle back inside, which preserves
+/// uniformity)
+fn sample_triangle_mesh(triangles: &[[[f64; 3]; 3]], m: usize, noise: Option<NoiseModel>) -> Vec<Vec<f64>> {
+    let areas: Vec<f64> = triangles.iter().map(triangle_area).collect();
+    let total_area: f64 = areas.iter().sum();
+    assert!(total_area > 0.0, "the level set f = 0 does not intersect the bounding box");
+
+    let mut rng = rand::thread_rng();
+    let mut pts = Vec::with_capacity(m);
+    for _ in 0..m {
+        let mut target = rng.gen::<f64>() * total_area;
+        let mut chosen = triangles.len() - 1;
+        for (idx, &area) in areas.iter().enumerate() {
+            if target < area {
+                chosen = idx;
+                break;
+            }
+            target -= area;
+        }
+        let [a, b, c] = triangles[chosen];
+        let (mut r1, mut r2): (f64, f64) = (rng.gen(), rng.gen());
+        if r1 + r2 > 1.0 {
+            r1 = 1.0 - r1;
+            r2 = 1.0 - r2;
+        }
+        pts.push(vec![
+            a[0] + r1 * (b[0] - a[0]) + r2 * (c[0] - a[0]),
+            a[1] + r1 * (b[1] - a[1]) + r2 * (c[1] - a[1]),
+            a[2] + r1 * (b[2] - a[2]) + r2 * (c[2] - a[2]),
+        ]);
+    }
+    add_noise(&mut pts, noise);
+    pts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The torus surface-area element is proportional to `major + minor·cos v`, so a
+    /// surface-uniform sample's `v` angle should be distributed with that density, not
+    /// uniformly — this is exactly the non-uniformity that the rejection sampling in
+    /// [`Torus::sample_boundary`] is meant to produce (as opposed to [`torus_naive`], whose
+    /// `v` is flat).
+    #[test]
+    fn torus_boundary_v_density_matches_jacobian() {
+        let (major, minor) = (3.0, 1.0);
+        let torus = Torus { major, minor };
+        let mut rng = rand::thread_rng();
+
+        let bins = 8;
+        let n = 40_000;
+        let mut counts = vec![0u32; bins];
+        for _ in 0..n {
+            let p = torus.sample_boundary(&mut rng);
+            let rho_xy = (p[0] * p[0] + p[1] * p[1]).sqrt();
+            let v = (p[2] / minor).atan2((rho_xy - major) / minor).rem_euclid(2.0 * PI);
+            counts[((v / (2.0 * PI) * bins as f64) as usize).min(bins - 1)] += 1;
+        }
+
+        let bin_width = 2.0 * PI / bins as f64;
+        for (i, &count) in counts.iter().enumerate() {
+            let (v0, v1) = (i as f64 * bin_width, (i + 1) as f64 * bin_width);
+            let expected_fraction = (major * (v1 - v0) + minor * (v1.sin() - v0.sin())) / (2.0 * PI * major);
+            let observed_fraction = count as f64 / n as f64;
+            assert!(
+                (observed_fraction - expected_fraction).abs() < 0.02,
+                "bin {i}: observed {observed_fraction:.4}, expected {expected_fraction:.4}"
+            );
+        }
+    }
+
+    /// Every subdivision level should produce a closed triangulated sphere: Euler
+    /// characteristic V - E + F = 2, with E = 3F/2 since each edge is shared by exactly 2
+    /// triangles.
+    #[test]
+    fn icosphere_euler_characteristic() {
+        for subdivisions in 0..=3 {
+            let (vertices, faces) = icosphere(subdivisions);
+            let edges = faces.len() * 3 / 2;
+            assert_eq!(
+                vertices.len() as i64 - edges as i64 + faces.len() as i64,
+                2,
+                "subdivisions = {subdivisions}"
+            );
+        }
+    }
+
+    /// Points extracted from the zero level set of `x^2 + y^2 + z^2 - 1` (the unit sphere)
+    /// should all lie close to radius 1. Since this `f` is a sum of per-axis terms, no cube
+    /// face has the checkerboard sign pattern, so [`marching_cube`]'s asymptotic-decider
+    /// branch is not reached here.
+    #[test]
+    fn implicit_surface_sphere_radius_error() {
+        let pts = implicit_surface(
+            |p| p[0] * p[0] + p[1] * p[1] + p[2] * p[2] - 1.0,
+            [-1.2..1.2, -1.2..1.2, -1.2..1.2],
+            24,
+            2_000,
+            None,
+        );
+        for p in &pts {
+            let r = (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt();
+            assert!((r - 1.0).abs() < 0.1, "point {p:?} has radius {r}, expected close to 1");
+        }
+    }
+}